@@ -2,7 +2,12 @@
 import argparse
 import json
 import re
+import shutil
 import sys
+import tempfile
+import urllib.parse
+import urllib.request
+from pathlib import Path
 
 
 SKIP_TAGS = {
@@ -19,6 +24,8 @@ SKIP_TAGS = {
 MATH_PATTERN = re.compile(r"(\${1,3}.*?\${1,3})", re.DOTALL)
 MATHISH_CHARS = set("$\\{}_^=<>|")
 SKIP_CLASS_NAMES = {
+    "sample-test",
+    "sample-tests",
     "header",
     "title",
     "time-limit",
@@ -50,9 +57,11 @@ def load_argos():
     return argostranslate.package, argostranslate.translate
 
 
-def should_skip_for_class(node) -> bool:
+def has_skipped_ancestor(node) -> bool:
     current = node.parent
     while current is not None:
+        if current.name in SKIP_TAGS:
+            return True
         classes = current.get("class", []) if hasattr(current, "get") else []
         if any(class_name in SKIP_CLASS_NAMES for class_name in classes):
             return True
@@ -122,20 +131,42 @@ def find_translator(from_code: str, to_code: str):
     return from_lang.get_translation(to_lang)
 
 
-def cmd_status(args: argparse.Namespace) -> None:
-    translator = find_translator(args.from_lang, args.to_lang)
-    if translator is None:
-        print(
-            json.dumps(
-                {
-                    "ready": False,
-                    "message": "Chinese statement support is not installed yet.",
-                }
-            )
-        )
-        return
+def status_payload(from_code: str, to_code: str) -> dict:
+    if find_translator(from_code, to_code) is None:
+        return {"ready": False, "message": "Chinese statement support is not installed yet."}
+    return {"ready": True, "message": "Chinese statement support is ready."}
 
-    print(json.dumps({"ready": True, "message": "Chinese statement support is ready."}))
+
+DOWNLOAD_PROGRESS_PREFIX = "__BINGOOJ_DOWNLOAD__"
+
+
+def download_package(item, directory: Path) -> Path:
+    """Downloads into `directory`, reporting byte progress on stderr for the install progress bar."""
+    links = getattr(item, "links", None) or []
+    if not links:
+        return Path(item.download())
+
+    url = links[0]
+    destination = directory / (Path(urllib.parse.urlparse(url).path).name or "package.argosmodel")
+    with urllib.request.urlopen(url) as response, open(destination, "wb") as output:
+        total = int(response.headers.get("Content-Length") or 0)
+        downloaded = 0
+        last_reported = 0
+        while True:
+            chunk = response.read(64 * 1024)
+            if not chunk:
+                break
+            output.write(chunk)
+            downloaded += len(chunk)
+            if downloaded - last_reported >= 512 * 1024:
+                last_reported = downloaded
+                log(f"{DOWNLOAD_PROGRESS_PREFIX} {downloaded} {total}")
+    log(f"{DOWNLOAD_PROGRESS_PREFIX} {downloaded} {total}")
+    return destination
+
+
+def cmd_status(args: argparse.Namespace) -> None:
+    print(json.dumps(status_payload(args.from_lang, args.to_lang)))
 
 
 def cmd_install(args: argparse.Namespace) -> None:
@@ -146,7 +177,7 @@ def cmd_install(args: argparse.Namespace) -> None:
 
     log("Updating Argos package index...")
     package.update_package_index()
-    log("Looking for an English -> Chinese package...")
+    log(f"Looking for a {args.from_lang} -> {args.to_lang} package...")
     available_packages = package.get_available_packages()
     matched_package = next(
         (
@@ -162,10 +193,11 @@ def cmd_install(args: argparse.Namespace) -> None:
             f"No Argos language package is available for {args.from_lang} -> {args.to_lang}."
         )
 
-    log("Downloading translation package...")
-    download_path = matched_package.download()
-    log("Installing translation package...")
-    package.install_from_path(download_path)
+    with tempfile.TemporaryDirectory(prefix="bingooj-argos-") as download_dir:
+        log("Downloading translation package...")
+        download_path = download_package(matched_package, Path(download_dir))
+        log("Installing translation package...")
+        package.install_from_path(download_path)
 
     if find_translator(args.from_lang, args.to_lang) is None:
         fail("Argos language package installation finished, but the translator is still unavailable.")
@@ -174,19 +206,84 @@ def cmd_install(args: argparse.Namespace) -> None:
     print(json.dumps({"ready": True, "message": "Chinese statement support is ready."}))
 
 
-def cmd_translate(args: argparse.Namespace) -> None:
-    translator = find_translator(args.from_lang, args.to_lang)
-    if translator is None:
-        fail("Chinese statement support is not installed yet.")
+def version_key(version) -> tuple:
+    parts = []
+    for part in str(version or "").split("."):
+        parts.append((0, int(part), "") if part.isdigit() else (1, 0, part))
+    return tuple(parts)
+
+
+def cmd_update(_args: argparse.Namespace) -> None:
+    package, _ = load_argos()
+    installed = package.get_installed_packages()
+    if not installed:
+        log("No translation packages are installed.")
+        print(json.dumps({"updated": [], "current": []}))
+        return
+
+    log("Updating Argos package index...")
+    package.update_package_index()
+    available = package.get_available_packages()
+
+    updated = []
+    current = []
+    for item in installed:
+        pair = f"{item.from_code} -> {item.to_code}"
+        candidate = next(
+            (
+                other
+                for other in available
+                if other.from_code == item.from_code and other.to_code == item.to_code
+            ),
+            None,
+        )
+        if candidate is None or version_key(candidate.package_version) <= version_key(
+            item.package_version
+        ):
+            log(f"{pair} is up to date ({item.package_version}).")
+            current.append(pair)
+            continue
 
+        with tempfile.TemporaryDirectory(prefix="bingooj-argos-") as download_dir:
+            log(f"Downloading {pair} {candidate.package_version}...")
+            download_path = download_package(candidate, Path(download_dir))
+
+            # Move the old package aside instead of deleting it so a failed install can roll back.
+            old_path = Path(item.package_path)
+            backup_path = old_path.with_name(f"{old_path.name}.bingooj-backup")
+            if backup_path.exists():
+                shutil.rmtree(backup_path)
+            old_path.rename(backup_path)
+            try:
+                log(f"Installing {pair} {candidate.package_version}...")
+                package.install_from_path(download_path)
+            except BaseException:
+                if old_path.exists():
+                    shutil.rmtree(old_path)
+                backup_path.rename(old_path)
+                raise
+            shutil.rmtree(backup_path, ignore_errors=True)
+        log(f"{pair} updated from {item.package_version} to {candidate.package_version}.")
+        updated.append(pair)
+
+    print(json.dumps({"updated": updated, "current": current}))
+
+
+SERVE_PROGRESS_PREFIX = "__BINGOOJ_PROGRESS__"
+
+
+class TranslationFailure(Exception):
+    pass
+
+
+def translate_html(translator, html: str, progress=None) -> str:
     try:
         from bs4 import BeautifulSoup, NavigableString
     except ImportError as exc:
-        fail(f"BeautifulSoup is required for local translation: {exc}")
+        raise TranslationFailure(f"BeautifulSoup is required for local translation: {exc}")
 
-    html = sys.stdin.read()
     if not html.strip():
-        fail("No HTML content was received for translation.")
+        raise TranslationFailure("No HTML content was received for translation.")
 
     def should_translate(node: NavigableString) -> bool:
         if not node.strip():
@@ -195,26 +292,137 @@ def cmd_translate(args: argparse.Namespace) -> None:
         parent = node.parent
         if parent is None:
             return False
-        if parent.name in SKIP_TAGS:
-            return False
         if parent.has_attr("class") and "MathJax" in parent.get("class", []):
             return False
-        if should_skip_for_class(node):
+        if has_skipped_ancestor(node):
             return False
         return True
 
     soup = BeautifulSoup(html, "html.parser")
     cache: dict[str, str] = {}
 
-    for node in soup.find_all(string=True):
-        if not isinstance(node, NavigableString) or not should_translate(node):
-            continue
+    segments = [
+        node
+        for node in soup.find_all(string=True)
+        if isinstance(node, NavigableString) and should_translate(node)
+    ]
+    total = len(segments)
+    if progress is not None:
+        progress(0, total)
 
+    for completed, node in enumerate(segments, start=1):
         original = str(node)
         translated = translate_text_preserving_math(translator, original, cache)
         node.replace_with(translated)
+        if progress is not None:
+            progress(completed, total)
+
+    return str(soup)
+
+
+def cmd_translate(args: argparse.Namespace) -> None:
+    translator = find_translator(args.from_lang, args.to_lang)
+    if translator is None:
+        fail("Chinese statement support is not installed yet.")
+
+    try:
+        sys.stdout.write(translate_html(translator, sys.stdin.read()))
+    except TranslationFailure as exc:
+        fail(str(exc))
 
-    sys.stdout.write(str(soup))
+
+def write_serve_response(payload: dict) -> None:
+    sys.stdout.write(json.dumps(payload))
+    sys.stdout.write("\n")
+    sys.stdout.flush()
+
+
+def write_serve_progress(completed: int, total: int) -> None:
+    log(f"{SERVE_PROGRESS_PREFIX} {completed} {total}")
+
+
+def handle_serve_request(request: dict, translators: dict) -> dict:
+    op = request.get("op")
+    from_lang = request.get("from", "en")
+    to_lang = request.get("to", "zh")
+    if op == "status":
+        return status_payload(from_lang, to_lang)
+    if op != "translate":
+        raise TranslationFailure(f"Unknown request: {op!r}")
+
+    key = (from_lang, to_lang)
+    translator = translators.get(key)
+    if translator is None:
+        translator = find_translator(from_lang, to_lang)
+        if translator is None:
+            raise TranslationFailure(
+                f"Translation support for {from_lang} -> {to_lang} is not installed yet."
+            )
+        translators[key] = translator
+    return {"html": translate_html(translator, request.get("html", ""), write_serve_progress)}
+
+
+def cmd_serve(_args: argparse.Namespace) -> None:
+    """One JSON request per stdin line, one JSON response per stdout line; progress goes to stderr."""
+    translators = {}
+    for line in sys.stdin:
+        if not line.strip():
+            continue
+        try:
+            request = json.loads(line)
+            if not isinstance(request, dict):
+                raise TranslationFailure("Translation requests must be JSON objects.")
+            write_serve_response({"ok": True, **handle_serve_request(request, translators)})
+        except SystemExit:
+            write_serve_response(
+                {
+                    "ok": False,
+                    "error": "Argos Translate runtime is unavailable. "
+                    "Please install local translation support first.",
+                }
+            )
+        except Exception as exc:
+            message = " ".join(str(exc).split()) or exc.__class__.__name__
+            write_serve_response({"ok": False, "error": message})
+
+
+def cmd_pairs(_args: argparse.Namespace) -> None:
+    _, translate = load_argos()
+    languages = translate.get_installed_languages()
+    pairs = []
+    for from_lang in languages:
+        for to_lang in languages:
+            if from_lang.code == to_lang.code:
+                continue
+            if from_lang.get_translation(to_lang) is None:
+                continue
+            pairs.append(
+                {
+                    "from": from_lang.code,
+                    "from_name": from_lang.name,
+                    "to": to_lang.code,
+                    "to_name": to_lang.name,
+                }
+            )
+
+    print(json.dumps(pairs))
+
+
+def cmd_list(_args: argparse.Namespace) -> None:
+    package, _ = load_argos()
+    packages = [
+        {
+            "from": item.from_code,
+            "from_name": item.from_name,
+            "to": item.to_code,
+            "to_name": item.to_name,
+            "version": str(getattr(item, "package_version", "") or ""),
+            "argos_version": str(getattr(item, "argos_version", "") or ""),
+        }
+        for item in package.get_installed_packages()
+    ]
+    packages.sort(key=lambda item: (item["from"], item["to"]))
+    print(json.dumps(packages))
 
 
 def build_parser() -> argparse.ArgumentParser:
@@ -237,6 +445,18 @@ def build_parser() -> argparse.ArgumentParser:
     add_lang_args(translate_parser)
     translate_parser.set_defaults(func=cmd_translate)
 
+    pairs_parser = subparsers.add_parser("pairs")
+    pairs_parser.set_defaults(func=cmd_pairs)
+
+    update_parser = subparsers.add_parser("update")
+    update_parser.set_defaults(func=cmd_update)
+
+    list_parser = subparsers.add_parser("list")
+    list_parser.set_defaults(func=cmd_list)
+
+    serve_parser = subparsers.add_parser("serve")
+    serve_parser.set_defaults(func=cmd_serve)
+
     return parser
 
 
@@ -1,4 +1,4 @@
-import { Fragment, useEffect, useMemo, useState } from "react";
+import { Fragment, useEffect, useMemo, useRef, useState } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
 import { cfListProblems } from "./oj/codeforces";
@@ -13,6 +13,7 @@ const STATEMENT_CACHE_KEY = "bingooj:cf:statement:v1";
 const STATEMENT_TRANSLATION_CACHE_KEY = "bingooj:cf:translation:v3";
 const DRAFT_CACHE_KEY = "bingooj:drafts:v1";
 const PROBLEM_LIST_CACHE_MAX_AGE = 1000 * 60 * 30;
+const PROBLEM_LIST_PAGE_SIZE = 200;
 const STATEMENT_CACHE_MAX_AGE = 1000 * 60 * 60 * 24 * 7;
 const MATH_DELIMITER_PATTERN = /(\${1,3})([\s\S]+?)\1/g;
 const MATH_SKIP_TAGS = new Set(["CODE", "KBD", "PRE", "SCRIPT", "STYLE", "TEXTAREA"]);
@@ -32,6 +33,27 @@ int main() {
   // TODO
   return 0;
 }
+`,
+  },
+  c: {
+    label: "C",
+    editorLanguage: "c",
+    template: `#include <stdio.h>
+
+int main(void) {
+  // TODO
+  return 0;
+}
+`,
+  },
+  kt: {
+    label: "Kotlin",
+    editorLanguage: "kotlin",
+    template: `fun main() {
+    val input = generateSequence(::readLine).joinToString("\\n")
+    // TODO
+    println(input)
+}
 `,
   },
   py: {
@@ -114,6 +136,48 @@ function readDrafts() {
   );
 }
 
+function errorMessage(error) {
+  if (error && typeof error === "object" && "message" in error) {
+    return String(error.message);
+  }
+  return String(error);
+}
+
+function formatVerifiedAgo(verifiedAt) {
+  if (!verifiedAt) return "";
+  const minutes = Math.max(0, Math.floor((Date.now() / 1000 - verifiedAt) / 60));
+  return minutes === 0 ? "刚刚验证" : `${minutes} 分钟前验证`;
+}
+
+// Progress events only carry new log lines; append them by sequence number. On a gap the
+// current lines are kept and the next full poll fills it in.
+function mergeInstallProgress(current, payload) {
+  const logs = current.logs ?? [];
+  const currentEnd = current.log_end_seq ?? 0;
+  const payloadStart = payload.log_start_seq ?? 0;
+  // A fresh install restarts the sequence.
+  if (payload.log_end_seq < currentEnd) {
+    return { ...current, ...payload };
+  }
+  if (payloadStart > currentEnd) {
+    return {
+      ...current,
+      ...payload,
+      logs,
+      log_start_seq: current.log_start_seq ?? 0,
+      log_end_seq: currentEnd,
+    };
+  }
+  const fresh = (payload.logs ?? []).slice(currentEnd - payloadStart);
+  const merged = [...logs, ...fresh].slice(-200);
+  return {
+    ...current,
+    ...payload,
+    logs: merged,
+    log_start_seq: payload.log_end_seq - merged.length,
+  };
+}
+
 function readCache(key) {
   try {
     const raw = localStorage.getItem(key);
@@ -332,6 +396,7 @@ export default function App() {
   const [statementLanguage, setStatementLanguage] = useState("en");
   const [translationLoading, setTranslationLoading] = useState(false);
   const [translationError, setTranslationError] = useState("");
+  const [translationProgress, setTranslationProgress] = useState({ completed: 0, total: 0 });
   const [translationSupport, setTranslationSupport] = useState({
     ready: false,
     installing: false,
@@ -346,6 +411,8 @@ export default function App() {
     phase: "Idle",
     error: "",
     logs: [],
+    downloaded_bytes: 0,
+    total_bytes: null,
   });
   const [problemDrafts, setProblemDrafts] = useState(() => readDrafts());
   const [connectBusy, setConnectBusy] = useState(false);
@@ -363,6 +430,13 @@ export default function App() {
   const [output, setOutput] = useState("Ready.");
   const [selectedSampleIndex, setSelectedSampleIndex] = useState(0);
   const [sampleResults, setSampleResults] = useState([]);
+  const [activeRunId, setActiveRunId] = useState(null);
+  const activeRunIdRef = useRef(null);
+  const runOutputsRef = useRef({});
+  const finishedRunsRef = useRef({});
+  const selectedBatchRef = useRef(new Set());
+  const editorRef = useRef(null);
+  const monacoRef = useRef(null);
   const [workspaceMode, setWorkspaceMode] = useState("samples");
 
   useEffect(() => {
@@ -383,18 +457,21 @@ export default function App() {
           setLoading(true);
         }
         setErr("");
-        const ps = await cfListProblems();
+        const page = await cfListProblems({
+          offset: 0,
+          limit: PROBLEM_LIST_PAGE_SIZE,
+        });
         if (!alive) return;
-        const sliced = ps.slice(0, 200);
+        const ps = page.problems;
         writeCache(PROBLEM_LIST_CACHE_KEY, {
           savedAt: Date.now(),
-          value: sliced,
+          value: ps,
         });
-        setProblems(sliced);
+        setProblems(ps);
         setSelectedId((current) =>
-          current && sliced.some((item) => item.id === current)
+          current && ps.some((item) => item.id === current)
             ? current
-            : sliced[0]?.id ?? ""
+            : ps[0]?.id ?? ""
         );
       } catch (e) {
         if (!alive) return;
@@ -422,7 +499,7 @@ export default function App() {
         }
       } catch (e) {
         if (alive) {
-          setConnectError(String(e));
+          setConnectError(errorMessage(e));
         }
       }
 
@@ -440,6 +517,35 @@ export default function App() {
     };
   }, []);
 
+  useEffect(() => {
+    let alive = true;
+    const unlistenPromise = listen("problem-received", (event) => {
+      if (!alive) return;
+      const payload = event.payload;
+      const id = payload.id || `CC-${payload.url || payload.title}`;
+      const received = {
+        id,
+        tags: [],
+        rating: null,
+        statementMd: payload.statement_md,
+        ...payload,
+        title: payload.title || "Imported Problem",
+        source: payload.source || "Competitive Companion",
+      };
+      setProblems((current) => [received, ...current.filter((item) => item.id !== id)]);
+      // A contest batch arrives as one POST per problem; stay on the first one.
+      if (!payload.batch || !selectedBatchRef.current.has(payload.batch.id)) {
+        if (payload.batch) selectedBatchRef.current.add(payload.batch.id);
+        setSelectedId(id);
+      }
+    });
+
+    return () => {
+      alive = false;
+      void unlistenPromise.then((unlisten) => unlisten());
+    };
+  }, []);
+
   const problem = useMemo(
     () => problems.find((p) => p.id === selectedId),
     [problems, selectedId]
@@ -555,11 +661,17 @@ export default function App() {
       }
     };
 
+    const unlistenPromise = listen("translation-install-progress", (event) => {
+      if (!alive) return;
+      setTranslationInstall((current) => mergeInstallProgress(current, event.payload));
+    });
+
     poll();
     const timer = window.setInterval(poll, 800);
     return () => {
       alive = false;
       window.clearInterval(timer);
+      void unlistenPromise.then((unlisten) => unlisten());
     };
   }, [translationInstall.active]);
 
@@ -605,7 +717,7 @@ export default function App() {
         );
       } catch (e) {
         if (!alive) return;
-        setStatementError(String(e));
+        setStatementError(errorMessage(e));
       } finally {
         if (!alive) return;
         setStatementLoading(false);
@@ -687,16 +799,37 @@ export default function App() {
     }
 
     let alive = true;
+    const unlistenPromise = listen("translate-progress", (event) => {
+      if (!alive) return;
+      setTranslationProgress(event.payload);
+    });
     (async () => {
       try {
         setTranslationLoading(true);
         setTranslationError("");
-        const translatedHtml = await invoke("translate_problem_html", {
-          html: problem.statement_html,
-          fromLang: "en",
-          toLang: "zh",
-        });
+        setTranslationProgress({ completed: 0, total: 0 });
+        const luogu =
+          problem.contestId && problem.index
+            ? await invoke("luogu_fetch_translation", {
+              contestId: Number(problem.contestId),
+              index: String(problem.index),
+            }).catch(() => null)
+            : null;
+        const translation = luogu?.found
+          ? luogu
+          : await invoke("translate_problem_html", {
+            html: problem.statement_html,
+            fromLang: problem.language || "en",
+            toLang: "zh",
+            detailed: true,
+          });
         if (!alive) return;
+        const translatedHtml = translation.html;
+        if (translation.missing_math?.length) {
+          setTranslationError(
+            `${translation.missing_math.length} formula(s) were dropped by the translator and re-inserted near their original place.`
+          );
+        }
 
         writeCachedStatementTranslation(problem.id, "zh", translatedHtml);
         setProblems((current) =>
@@ -714,7 +847,7 @@ export default function App() {
         );
       } catch (e) {
         if (!alive) return;
-        setTranslationError(String(e));
+        setTranslationError(errorMessage(e));
       } finally {
         if (!alive) return;
         setTranslationLoading(false);
@@ -723,6 +856,7 @@ export default function App() {
 
     return () => {
       alive = false;
+      void unlistenPromise.then((unlisten) => unlisten());
     };
   }, [
     problem?.id,
@@ -761,6 +895,16 @@ export default function App() {
     }));
   }
 
+  async function cancelTranslationInstall() {
+    try {
+      const installState = await invoke("cancel_translation_install");
+      setTranslationInstall(installState);
+    } catch (e) {
+      setTranslationError(String(e));
+    }
+    await refreshTranslationSupport();
+  }
+
   async function openCodeforcesLoginWindow() {
     try {
       setConnectBusy(true);
@@ -771,7 +915,7 @@ export default function App() {
       setConnectBusy(false);
     } catch (e) {
       setConnectBusy(false);
-      setConnectError(String(e));
+      setConnectError(errorMessage(e));
     }
   }
 
@@ -789,7 +933,7 @@ export default function App() {
         message: "提交前请先登录",
       });
     } catch (error) {
-      setConnectError(String(error));
+      setConnectError(errorMessage(error));
     } finally {
       setLogoutBusy(false);
     }
@@ -819,28 +963,125 @@ export default function App() {
     }));
   }
 
-  async function executeCode(nextStdin) {
-    return invoke("run_code", {
-      lang,
-      code,
-      stdin: nextStdin,
-    });
+  function showDiagnostics(diagnostics) {
+    const editor = editorRef.current;
+    const monaco = monacoRef.current;
+    const model = editor?.getModel();
+    if (!editor || !monaco || !model) return;
+
+    const severities = {
+      fatal_error: monaco.MarkerSeverity.Error,
+      error: monaco.MarkerSeverity.Error,
+      warning: monaco.MarkerSeverity.Warning,
+      note: monaco.MarkerSeverity.Info,
+    };
+    const markers = (diagnostics ?? [])
+      .filter((d) => d.line >= 1 && d.line <= model.getLineCount())
+      .map((d) => ({
+        severity: severities[d.severity] ?? monaco.MarkerSeverity.Error,
+        message: d.message,
+        startLineNumber: d.line,
+        startColumn: d.col ?? 1,
+        endLineNumber: d.line,
+        endColumn: model.getLineMaxColumn(d.line),
+      }));
+    monaco.editor.setModelMarkers(model, "compiler", markers);
+
+    const first = markers.find((m) => m.severity === monaco.MarkerSeverity.Error);
+    if (first) {
+      editor.revealLineInCenter(first.startLineNumber);
+      editor.setPosition({ lineNumber: first.startLineNumber, column: first.startColumn });
+    }
   }
 
+  useEffect(() => {
+    const outputPromise = listen("run-output", (event) => {
+      const { run_id: runId, chunk } = event.payload;
+      const text = (runOutputsRef.current[runId] ?? "") + chunk;
+      runOutputsRef.current[runId] = text;
+      if (runId === activeRunIdRef.current) {
+        setOutput(text);
+      }
+    });
+    const finishedPromise = listen("run-finished", (event) => {
+      const { run_id: runId, status, message, diagnostics } = event.payload;
+      const text = runOutputsRef.current[runId] ?? "";
+      delete runOutputsRef.current[runId];
+      if (status === "compile_error") {
+        showDiagnostics(diagnostics);
+      }
+
+      let finalText = text;
+      if (message) {
+        finalText = text && !text.endsWith("\n") ? `${text}\n${message}` : text + message;
+      } else if (!text.trim()) {
+        finalText = status === "ok" ? "OK\n" : "Error\n";
+      }
+      if (runId !== activeRunIdRef.current) {
+        finishedRunsRef.current[runId] = finalText;
+        return;
+      }
+      setOutput(finalText);
+      activeRunIdRef.current = null;
+      setActiveRunId(null);
+    });
+
+    return () => {
+      void outputPromise.then((unlisten) => unlisten());
+      void finishedPromise.then((unlisten) => unlisten());
+    };
+  }, []);
+
   async function runOnce() {
     try {
       setSampleResults([]);
       setWorkspaceMode("custom");
       setOutput(`Running ${currentLanguage.label}...`);
-      const out = await executeCode(stdin);
-      setOutput(String(out));
+      showDiagnostics([]);
+      activeRunIdRef.current = null;
+      const runId = await invoke("run_code_streaming", {
+        lang,
+        code,
+        stdin,
+      });
+      if (runId in finishedRunsRef.current) {
+        setOutput(finishedRunsRef.current[runId]);
+        delete finishedRunsRef.current[runId];
+        return;
+      }
+      activeRunIdRef.current = runId;
+      setActiveRunId(runId);
+      if (runOutputsRef.current[runId]) {
+        setOutput(runOutputsRef.current[runId]);
+      }
     } catch (e) {
       setWorkspaceMode("custom");
       setOutput(String(e));
     }
   }
 
+  async function stopRun() {
+    if (!activeRunId) return;
+    try {
+      await invoke("cancel_run", { runId: activeRunId });
+    } catch (e) {
+      setOutput(String(e));
+    }
+  }
+
   async function runSamples() {
+    const interactive = Boolean(problem?.interactive);
+    if (
+      interactive &&
+      !window.confirm(
+        "This is an interactive problem. Plain sample comparison will not reflect the judge. Run anyway?"
+      )
+    ) {
+      setWorkspaceMode("custom");
+      setOutput("Interactive problem: sample comparison skipped.");
+      return;
+    }
+
     setWorkspaceMode("samples");
     setOutput(`Running ${currentLanguage.label} samples...`);
 
@@ -850,36 +1091,48 @@ export default function App() {
       return;
     }
 
-    const results = [];
-    let passed = 0;
-    for (let i = 0; i < samples.length; i++) {
-      const s = samples[i];
-      try {
-        const out = await executeCode(s.input);
-        const got = String(out).replace(/\r\n/g, "\n");
-        const exp = String(s.output).replace(/\r\n/g, "\n");
+    let batch;
+    try {
+      batch = await invoke("run_against_samples", {
+        lang,
+        code,
+        samples: samples.map((s) => ({ input: s.input, output: s.output })),
+        interactive,
+        force: interactive,
+      });
+    } catch (e) {
+      setSampleResults([]);
+      setOutput(errorMessage(e));
+      return;
+    }
+    showDiagnostics(batch.diagnostics);
 
-        const ok = got.trimEnd() === exp.trimEnd();
-        if (ok) passed += 1;
-        results.push({
-          index: i,
-          input: s.input,
-          expected: s.output,
-          got,
-          ok,
-          error: "",
-        });
-      } catch (e) {
-        results.push({
+    const results = samples.map((s, i) => {
+      const run = batch.results[i];
+      if (batch.compile_output != null || !run) {
+        return {
           index: i,
           input: s.input,
           expected: s.output,
-          got: "",
+          got: batch.compile_output ?? "",
           ok: false,
-          error: String(e),
-        });
+          error: "",
+          diff: "",
+          firstMismatchLine: null,
+        };
       }
-    }
+      return {
+        index: i,
+        input: s.input,
+        expected: s.output,
+        got: String(run.got).replace(/\r\n/g, "\n"),
+        ok: run.ok,
+        error: run.error,
+        diff: run.diff,
+        firstMismatchLine: run.first_mismatch_line,
+      };
+    });
+    const passed = results.filter((result) => result.ok).length;
     const firstFailedIndex = results.findIndex((result) => !result.ok);
     setSampleResults(results);
     setSelectedSampleIndex(firstFailedIndex >= 0 ? firstFailedIndex : 0);
@@ -932,7 +1185,10 @@ export default function App() {
       }
     } catch (e) {
       setWorkspaceMode("custom");
-      setOutput(String(e));
+      setOutput(errorMessage(e));
+      if (e?.kind === "AuthExpired") {
+        void openCodeforcesLoginWindow();
+      }
     } finally {
       setSubmitBusy(false);
     }
@@ -971,7 +1227,7 @@ export default function App() {
               key={p.id}
               className={"item " + (p.id === selectedId ? "active" : "")}
               onClick={() => setSelectedId(p.id)}
-              title={p.id}
+              title={p.contestName ?? p.id}
             >
               <div className="item-row">
                 <div className="title">{p.title}</div>
@@ -980,6 +1236,7 @@ export default function App() {
               <div className="meta">
                 <span>{p.id}</span>
                 {p.rating ? <span>{p.rating}</span> : null}
+                {p.division ? <span>{p.division}</span> : null}
               </div>
             </button>
           ))}
@@ -996,6 +1253,9 @@ export default function App() {
               {authStatus.checking
                 ? "正在检查登录状态..."
                 : authStatus.message}
+              {!authStatus.checking && authStatus.verified_at ? (
+                <span className="account-verified"> · {formatVerifiedAgo(authStatus.verified_at)}</span>
+              ) : null}
             </div>
             <button
               className="btn subtle account-btn"
@@ -1041,6 +1301,8 @@ export default function App() {
                   }}
                 >
                   <option value="cpp">C++</option>
+                  <option value="c">C</option>
+                  <option value="kt">Kotlin</option>
                   <option value="py">Python</option>
                   <option value="js">JavaScript</option>
                 </select>
@@ -1048,9 +1310,15 @@ export default function App() {
               <div className="control-group actions">
                 <div className="control-label">运行</div>
                 <div className="action-row">
-                  <button className="btn primary" onClick={runOnce}>
-                    Run
-                  </button>
+                  {activeRunId ? (
+                    <button className="btn primary" onClick={stopRun}>
+                      Stop
+                    </button>
+                  ) : (
+                    <button className="btn primary" onClick={runOnce}>
+                      Run
+                    </button>
+                  )}
                   <button
                     className="btn core"
                     onClick={submitSolution}
@@ -1145,6 +1413,27 @@ export default function App() {
                         }}
                       />
                     </div>
+                    {translationInstall.active && translationInstall.total_bytes ? (
+                      <div className="install-progress-head">
+                        <div className="install-phase">Downloading runtime</div>
+                        <div className="install-step">
+                          {Math.floor(
+                            (translationInstall.downloaded_bytes / translationInstall.total_bytes) * 100
+                          )}
+                          %
+                        </div>
+                      </div>
+                    ) : null}
+                    {translationInstall.active && translationInstall.total_bytes ? (
+                      <div className="install-progress-bar">
+                        <div
+                          className="install-progress-fill"
+                          style={{
+                            width: `${(translationInstall.downloaded_bytes / translationInstall.total_bytes) * 100}%`,
+                          }}
+                        />
+                      </div>
+                    ) : null}
                     {translationInstall.logs?.length ? (
                       <div className="install-log">
                         {translationInstall.logs.map((line, index) => (
@@ -1167,13 +1456,41 @@ export default function App() {
                       ? "Installing..."
                       : "Set Up Chinese Statement Support"}
                   </button>
+                  {translationInstall.active ? (
+                    <button className="btn subtle" onClick={cancelTranslationInstall}>
+                      Cancel Install
+                    </button>
+                  ) : null}
                   <button className="btn subtle" onClick={() => setStatementLanguage("en")}>
                     Use English Instead
                   </button>
                 </div>
               </div>
             ) : statementLanguage === "zh" && translationLoading && !displayedStatementHtml ? (
-              <div className="statement-status">Translating statement locally...</div>
+              <div className="statement-status">
+                <div>Translating statement locally...</div>
+                {translationProgress.total > 0 ? (
+                  <div className="install-progress">
+                    <div className="install-progress-head">
+                      <div className="install-phase">Translating segments</div>
+                      <div className="install-step">
+                        {translationProgress.completed}/{translationProgress.total}
+                      </div>
+                    </div>
+                    <div className="install-progress-bar">
+                      <div
+                        className="install-progress-fill"
+                        style={{
+                          width: `${Math.max(
+                            8,
+                            (translationProgress.completed / translationProgress.total) * 100
+                          )}%`,
+                        }}
+                      />
+                    </div>
+                  </div>
+                ) : null}
+              </div>
             ) : statementLanguage === "zh" && translationError && !displayedStatementHtml ? (
               <div className="statement-status error">
                 <div className="statement-status-title">Local translation failed.</div>
@@ -1215,6 +1532,10 @@ export default function App() {
                   language={currentLanguage.editorLanguage}
                   value={code}
                   onChange={(v) => updateCode(v ?? "")}
+                  onMount={(editor, monaco) => {
+                    editorRef.current = editor;
+                    monacoRef.current = monaco;
+                  }}
                   theme="vs-dark"
                   options={{
                     automaticLayout: true,
@@ -1312,8 +1633,12 @@ export default function App() {
                   {selectedDiff ? (
                     <div className="diff-panel">
                       <div className="diff-summary">
-                        First difference at line {selectedDiff.firstMismatchLine}.
+                        First difference at line{" "}
+                        {selectedResult.firstMismatchLine ?? selectedDiff.firstMismatchLine}.
                       </div>
+                      {selectedResult.diff ? (
+                        <pre className="diff-unified">{selectedResult.diff}</pre>
+                      ) : null}
                       <div className="diff-table">
                         <div className="diff-table-head">Expected</div>
                         <div className="diff-table-head">Got</div>
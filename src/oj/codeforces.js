@@ -1,5 +1,6 @@
 import { invoke } from "@tauri-apps/api/core";
 
-export async function cfListProblems() {
-  return invoke("cf_list_problems");
+// Resolves to `{ total_matched, offset, problems }` for the requested page.
+export async function cfListProblems(query = {}) {
+  return invoke("cf_list_problems", { query });
 }